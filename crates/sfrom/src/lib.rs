@@ -1,44 +1,66 @@
 use std::io::{self, Seek, SeekFrom, Write};
+use std::mem::size_of;
+
 use nom::{
     bytes::complete::{tag, take},
-    number::complete::{le_u16, le_u32, le_u8},
-    sequence::tuple,
+    number::complete::{le_u16, le_u8},
     IResult,
 };
+use zerocopy::byteorder::little_endian::{U16, U32};
+use zerocopy::{AsBytes, FromBytes, FromZeroes};
 
-#[derive(Debug)]
+/// Compile-time assertion, used to pin the on-disk layout of the
+/// fixed-size header/footer structs to the sizes the format mandates.
+macro_rules! static_assert {
+    ($cond:expr $(,)?) => {
+        const _: () = assert!($cond);
+    };
+}
+
+/// The fixed 0x30-byte SFROM header.
+///
+/// Laid out exactly as it appears on disk: every multi-byte field is a
+/// little-endian `byteorder` wrapper (alignment 1), so a `#[repr(C)]`
+/// struct has no padding and can be viewed directly over the input slice.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, FromBytes, FromZeroes, AsBytes)]
 pub struct SfromHeader {
-    pub magic: u32, // 0x00000100
-    pub file_size: u32,
-    pub rom_location: u32, // Usually 0x30
-    pub pcm_samples_location: u32,
-    pub pcm_footer_location: u32,
-    pub footer_location: u32,
-    pub sdd1_data_offset: u32,
-    pub reserved1: u32, // 0x00000000
+    pub magic: U32, // 0x00000100
+    pub file_size: U32,
+    pub rom_location: U32, // Usually 0x30
+    pub pcm_samples_location: U32,
+    pub pcm_footer_location: U32,
+    pub footer_location: U32,
+    pub sdd1_data_offset: U32,
+    pub reserved1: U32, // 0x00000000
     // unknown flag
-    pub unknown1: u32,
+    pub unknown1: U32,
     // 0x8
     pub wiiu_game_id: [u8; 8],
-    pub reserved2: u32, // 0x00000000
+    pub reserved2: U32, // 0x00000000
 }
 
-#[derive(Debug)]
+static_assert!(size_of::<SfromHeader>() == 0x30);
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, FromBytes, FromZeroes, AsBytes)]
 pub struct SfromFooter {
     pub fps: u8, // 0x3C = 60fps, 0x32 = 50fps
-    pub rom_size: u32,
-    pub pcm_samples_size: u32,
-    pub pcm_footer_size: u32,
-    pub preset_id: u16,
+    pub rom_size: U32,
+    pub pcm_samples_size: U32,
+    pub pcm_footer_size: U32,
+    pub preset_id: U16,
     pub player_count: u8,
     pub sound_volume: u8,
     pub rom_type: u8, // 0x14 = LoROM, 0x15 = HiROM
     pub enhancement_chip: u8,
-    pub unknown1: u32, // Usually 0x1
-    pub unknown2: u32, // Always 0x1
+    pub unknown1: U32, // Usually 0x1
+    pub unknown2: U32, // Always 0x1
 }
 
-#[derive(Debug)]
+static_assert!(size_of::<SfromFooter>() == 0x1B);
+
+#[derive(Debug, Default, PartialEq, Eq)]
 pub struct GameTagData {
     /// Threshold for Armet,
     /// the Epilepsy reduction filter
@@ -62,6 +84,7 @@ pub struct GameTagData {
 }
 
 #[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnhancementChip {
     Normal = 0x00,
     Dsp1 = 0x02,
@@ -77,102 +100,302 @@ pub enum EnhancementChip {
     SuperFx = 0x0C,
 }
 
+/// SNES cartridge memory map, as detected from the internal header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapMode {
+    LoRom,
+    HiRom,
+    ExHiRom,
+}
 
-impl SfromHeader {
-    pub fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (
-            input,
-            (
-                magic,
-                file_size,
-                rom_location,
-                pcm_samples_location,
-                pcm_footer_location,
-                footer_location,
-                sdd1_data_offset,
-                reserved1,
-                unknown1,
-                wiiu_game_id,
-                reserved2,
-            ),
-        ) = tuple((
-            le_u32,
-            le_u32,
-            le_u32,
-            le_u32,
-            le_u32,
-            le_u32,
-            le_u32,
-            le_u32,
-            le_u32,
-            take(8usize),
-            le_u32,
-        ))(input)?;
+/// A decoded SNES internal cartridge header.
+///
+/// This reads the header out of a raw `.sfc`/`.smc` ROM so the rest of the
+/// crate can derive `rom_type`, the enhancement chip, SRAM size and title
+/// metadata without the caller having to know them up front.
+#[derive(Debug, Clone)]
+pub struct SnesRomHeader {
+    /// Detected memory map (LoROM/HiROM/ExHiROM).
+    pub map_mode: MapMode,
+    /// Raw 21-byte internal title (space-padded, shift-JIS/ASCII).
+    pub title: [u8; 21],
+    /// ROM size in bytes (`0x400 << byte[0x17]`).
+    pub rom_size: u32,
+    /// SRAM size in bytes, or 0 when the cartridge has none.
+    pub sram_size: u32,
+    /// Two-byte maker/licensee code at header offset 0x1A.
+    pub maker_code: [u8; 2],
+    /// Chipset / ROM-type byte at header offset 0x16.
+    pub chipset: u8,
+}
 
-        Ok((
-            input,
-            SfromHeader {
-                magic,
-                file_size,
-                rom_location,
-                pcm_samples_location,
-                pcm_footer_location,
-                footer_location,
-                sdd1_data_offset,
-                reserved1,
-                unknown1,
-                wiiu_game_id: wiiu_game_id.try_into().unwrap(),
-                reserved2,
+impl SnesRomHeader {
+    /// Locate and decode the internal header of a raw SNES ROM.
+    ///
+    /// Mirrors snes9x's `memmap` score-based detection: a 0x200-byte copier
+    /// header is stripped first, then each candidate map mode is scored and
+    /// the highest scorer wins (ties break toward LoROM). Returns `None` when
+    /// no candidate header fits in the supplied data, when the best candidate
+    /// scores below `MIN_SCORE`, or when its ROM/SRAM size bytes are out of
+    /// range.
+    pub fn parse(rom: &[u8]) -> Option<Self> {
+        // Strip a SMC copier header if one is present.
+        let data = if rom.len() % 0x8000 == 0x200 {
+            &rom[0x200..]
+        } else {
+            rom
+        };
+
+        // (map mode, header offset, expected low nibble of map-mode byte)
+        let candidates = [
+            (MapMode::LoRom, 0x7FC0usize, 0x0u8),
+            (MapMode::HiRom, 0xFFC0, 0x1),
+            (MapMode::ExHiRom, 0x40FFC0, 0x5),
+        ];
+
+        let mut best: Option<(i32, MapMode, usize)> = None;
+        for (mode, offset, expected) in candidates {
+            let Some(header) = data.get(offset..offset + 64) else {
+                continue;
+            };
+            let score = Self::score(header, expected);
+            // Strictly-greater keeps the earliest (LoROM-first) candidate on ties.
+            if best.map_or(true, |(best_score, _, _)| score > best_score) {
+                best = Some((score, mode, offset));
+            }
+        }
+
+        // Require the winner to clear a minimum score, otherwise a non-SNES
+        // file would always match the first candidate with score 0.
+        let (score, map_mode, offset) = best?;
+        if score < Self::MIN_SCORE {
+            return None;
+        }
+        let header = &data[offset..offset + 64];
+
+        // Reject implausible size bytes: besides being garbage, a large shift
+        // would overflow `0x400 << byte`.
+        let rom_size_byte = header[0x17];
+        let sram_size_byte = header[0x18];
+        if rom_size_byte > 0x0D || sram_size_byte > 0x09 {
+            return None;
+        }
+
+        Some(SnesRomHeader {
+            map_mode,
+            title: header[0x00..0x15].try_into().unwrap(),
+            rom_size: 0x400u32 << rom_size_byte,
+            sram_size: if sram_size_byte == 0 {
+                0
+            } else {
+                0x400u32 << sram_size_byte
             },
-        ))
+            maker_code: [header[0x1A], header[0x1B]],
+            chipset: header[0x16],
+        })
+    }
+
+    /// Minimum candidate score accepted as a real header. A genuine ROM
+    /// clears this easily (checksum pair + reset vector + printable title),
+    /// while random data almost never does.
+    const MIN_SCORE: i32 = 6;
+
+    /// Score a candidate 64-byte internal header against a map mode.
+    fn score(header: &[u8], expected_mode: u8) -> i32 {
+        let mut score = 0;
+
+        // (a) checksum and its complement must XOR to 0xFFFF.
+        let complement = u16::from_le_bytes([header[0x1C], header[0x1D]]);
+        let checksum = u16::from_le_bytes([header[0x1E], header[0x1F]]);
+        if complement ^ checksum == 0xFFFF {
+            score += 4;
+        }
+
+        // (b) reset vector should point into ROM space.
+        let reset = u16::from_le_bytes([header[0x3C], header[0x3D]]);
+        if reset >= 0x8000 {
+            score += 2;
+        }
+
+        // (c) map-mode byte's low nibble should match the candidate.
+        if header[0x15] & 0x0F == expected_mode {
+            score += 2;
+        }
+
+        // (d) the title should be printable ASCII.
+        if header[0x00..0x15]
+            .iter()
+            .all(|b| b.is_ascii_graphic() || *b == b' ')
+        {
+            score += 2;
+        }
+
+        score
     }
 }
 
-impl SfromFooter {
+impl SnesRomHeader {
+    /// Map the chipset byte (header offset 0x16) to the crate's
+    /// [`EnhancementChip`].
+    ///
+    /// Follows snes9x's decoding: the low nibble of the chipset byte gives the
+    /// memory layout (0=ROM, 1=ROM+RAM, 2=ROM+RAM+Battery) and only a value of
+    /// 3 or more marks the presence of a coprocessor, whose family the high
+    /// nibble then selects. The SA-1 sub-variant and custom-chip subtype are
+    /// disambiguated by the ROM size and maker code. Returns
+    /// [`EnhancementChip::Normal`] when no coprocessor is present.
+    pub fn enhancement_chip(&self) -> EnhancementChip {
+        // A low nibble below 3 is plain ROM/RAM/battery — no coprocessor.
+        if self.chipset & 0x0F < 3 {
+            return EnhancementChip::Normal;
+        }
+        match self.chipset >> 4 {
+            // DSP family. DSP1 is overwhelmingly the common revision; the
+            // others share no dedicated footer byte here.
+            0x0 => EnhancementChip::Dsp1,
+            // SuperFX / GSU.
+            0x1 => EnhancementChip::SuperFx,
+            // SA-1. Real SA-1 carts carry ROM-type IDs in both the 0x2x range
+            // (shared with OBC1) and the 0x3x range, so both resolve here.
+            0x2 | 0x3 => self.sa1_variant(),
+            // S-DD1.
+            0x4 => EnhancementChip::Sdd1,
+            // S-RTC has no dedicated footer variant.
+            0x5 => EnhancementChip::Normal,
+            // Custom chip — maker code 0x33 plus a subtype in the low nibble.
+            0xF => self.custom_chip(),
+            _ => EnhancementChip::Normal,
+        }
+    }
+
+    /// SFROM footer ROM-type byte for this map mode (0x14 LoROM, 0x15 HiROM).
+    pub fn rom_type(&self) -> u8 {
+        match self.map_mode {
+            MapMode::LoRom => 0x14,
+            MapMode::HiRom | MapMode::ExHiRom => 0x15,
+        }
+    }
+
+    /// Resolve the SA-1 sub-variant from the ROM size.
+    fn sa1_variant(&self) -> EnhancementChip {
+        match self.rom_size {
+            0..=0x100000 => EnhancementChip::Sa1_1,
+            ..=0x200000 => EnhancementChip::Sa1_2,
+            ..=0x300000 => EnhancementChip::Sa1_3,
+            ..=0x400000 => EnhancementChip::Sa1_4,
+            ..=0x500000 => EnhancementChip::Sa1_5,
+            _ => EnhancementChip::Sa1_6,
+        }
+    }
+
+    /// Resolve a custom (0xF-family) coprocessor from its subtype nibble.
+    fn custom_chip(&self) -> EnhancementChip {
+        // A maker-code byte of 0x33 marks the extended custom-chip header.
+        if self.maker_code[0] != 0x33 {
+            return EnhancementChip::Normal;
+        }
+        match self.chipset & 0x0F {
+            0x3 => EnhancementChip::Cx4,
+            // SPC7110 and friends have no dedicated footer variant here.
+            _ => EnhancementChip::Normal,
+        }
+    }
+
+    /// Offset of the internal header within a copier-header-free ROM.
+    pub fn header_offset(&self) -> usize {
+        match self.map_mode {
+            MapMode::LoRom => 0x7FC0,
+            MapMode::HiRom => 0xFFC0,
+            MapMode::ExHiRom => 0x40FFC0,
+        }
+    }
+
+    /// Recompute the 16-bit SNES ROM checksum.
+    ///
+    /// Every byte is summed modulo 0x10000. A non-power-of-two ROM is split
+    /// into its largest power-of-two prefix plus a remainder that is mirrored
+    /// (doubled) up to the prefix size, matching the cartridge hardware.
+    pub fn checksum(rom: &[u8]) -> u16 {
+        fn sum(bytes: &[u8]) -> u32 {
+            bytes.iter().map(|&b| b as u32).sum()
+        }
+
+        fn mirrored_sum(rom: &[u8]) -> u32 {
+            let len = rom.len();
+            if len == 0 {
+                return 0;
+            }
+            // Largest power of two that is <= len.
+            let mut prefix = 1usize;
+            while prefix * 2 <= len {
+                prefix *= 2;
+            }
+            if prefix == len {
+                return sum(rom);
+            }
+            let remainder = len - prefix;
+            // Mirror the remainder up to the prefix size.
+            sum(&rom[..prefix]) + mirrored_sum(&rom[prefix..]) * (prefix / remainder) as u32
+        }
+
+        (mirrored_sum(rom) & 0xFFFF) as u16
+    }
+}
+
+/// Errors reported by [`Sfrom::verify`].
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError {
+    #[error("bad magic: expected 0x00000100, found {0:#010x}")]
+    BadMagic(u32),
+    #[error("offsets are not monotonic: {0}")]
+    NonMonotonicOffsets(String),
+    #[error("footer rom_size {declared} does not match rom data length {actual}")]
+    RomSizeMismatch { declared: u32, actual: usize },
+    #[error("pcm {kind} size {declared} does not match slice length {actual}")]
+    PcmSizeMismatch {
+        kind: &'static str,
+        declared: u32,
+        actual: usize,
+    },
+    #[error("could not locate SNES internal header for checksum")]
+    MissingSnesHeader,
+    #[error("checksum mismatch: computed {computed:#06x}, header checksum {stored:#06x}")]
+    ChecksumMismatch { computed: u16, stored: u16 },
+    #[error("checksum complement {complement:#06x} is not the complement of {checksum:#06x}")]
+    ComplementMismatch { complement: u16, checksum: u16 },
+}
+
+impl SfromHeader {
+    /// Validate and borrow-then-copy the header out of the front of `input`.
+    ///
+    /// The layout is checked by `zerocopy`; there are no per-field reads and
+    /// no panicking `try_into`.
     pub fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let (
-            input,
-            (
-                fps,
-                rom_size,
-                pcm_samples_size,
-                pcm_footer_size,
-                preset_id,
-                player_count,
-                sound_volume,
-                rom_type,
-                enhancement_chip,
-                unknown1,
-                unknown2,
-            ),
-        ) = tuple((
-            le_u8, le_u32, le_u32, le_u32, le_u16, le_u8, le_u8, le_u8, le_u8, le_u32, le_u32,
-        ))(input)?;
+        match Self::read_from_prefix(input) {
+            Some(header) => Ok((&input[size_of::<Self>()..], header)),
+            None => Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Eof,
+            ))),
+        }
+    }
+}
 
-        Ok((
-            input,
-            SfromFooter {
-                fps,
-                rom_size,
-                pcm_samples_size,
-                pcm_footer_size,
-                preset_id,
-                player_count,
-                sound_volume,
-                rom_type,
-                enhancement_chip,
-                unknown1,
-                unknown2,
-            },
-        ))
+impl SfromFooter {
+    pub fn parse(input: &[u8]) -> IResult<&[u8], Self> {
+        match Self::read_from_prefix(input) {
+            Some(footer) => Ok((&input[size_of::<Self>()..], footer)),
+            None => Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Eof,
+            ))),
+        }
     }
 }
 
 impl GameTagData {
     fn parse_tag_a(input: &[u8]) -> IResult<&[u8], [u8; 3]> {
-        let (input, _) = tag("A")(input)?;
-        let (input, data) = take(3usize)(input)?;
-        Ok((input, data.try_into().unwrap()))
+        Self::parse_tag_bytes::<3>(input, "A")
     }
 
     fn parse_tag_d(input: &[u8]) -> IResult<&[u8], Vec<u8>> {
@@ -189,54 +412,212 @@ impl GameTagData {
         Ok((input, preset_id))
     }
 
-    // Add more tag parsers as needed...
+    /// Parse a fixed-width byte-array tag (`letter` followed by `N` bytes).
+    fn parse_tag_bytes<const N: usize>(input: &[u8], letter: &str) -> IResult<&[u8], [u8; N]> {
+        let (input, _) = tag(letter)(input)?;
+        let (input, data) = take(N)(input)?;
+        Ok((input, data.try_into().unwrap()))
+    }
+
+    /// Parse a single-byte value tag (`letter` followed by one byte).
+    fn parse_tag_u8(input: &[u8], letter: &str) -> IResult<&[u8], u8> {
+        let (input, _) = tag(letter)(input)?;
+        le_u8(input)
+    }
+
+    /// Parse a little-endian `u16` value tag (`letter` followed by two bytes).
+    fn parse_tag_u16(input: &[u8], letter: &str) -> IResult<&[u8], u16> {
+        let (input, _) = tag(letter)(input)?;
+        le_u16(input)
+    }
 
     pub fn parse(input: &[u8]) -> IResult<&[u8], Self> {
-        let mut tag_data = GameTagData {
-            armet_threshold: None,
-            sdd1_data: None,
-            preset_id: None,
-            flags: None,
-            unknown_s: None,
-            superfx_clock: None,
-            armet_version: None,
-            snes_header_location: None,
-            unknown_d: None,
-            enhancement_chip: None,
-            resolution_ratio: None,
-            unknown_j: None,
-            mouse_flag: None,
-            max_players: None,
-            visible_height: None,
-            unknown_t: None,
-            volume: None,
-        };
+        let mut tag_data = GameTagData::default();
 
         let mut remaining = input;
         while !remaining.is_empty() {
             match remaining[0] as char {
                 'A' => {
-                    let (new_input, data) = Self::parse_tag_a(remaining)?;
+                    let (rest, data) = Self::parse_tag_a(remaining)?;
                     tag_data.armet_threshold = Some(data);
-                    remaining = new_input;
+                    remaining = rest;
                 }
                 'D' => {
-                    let (new_input, data) = Self::parse_tag_d(remaining)?;
+                    let (rest, data) = Self::parse_tag_d(remaining)?;
                     tag_data.sdd1_data = Some(data);
-                    remaining = new_input;
+                    remaining = rest;
                 }
                 'G' => {
-                    let (new_input, data) = Self::parse_tag_g(remaining)?;
+                    let (rest, data) = Self::parse_tag_g(remaining)?;
                     tag_data.preset_id = Some(data);
-                    remaining = new_input;
+                    remaining = rest;
+                }
+                'P' => {
+                    let (rest, data) = Self::parse_tag_bytes::<7>(remaining, "P")?;
+                    tag_data.flags = Some(data);
+                    remaining = rest;
+                }
+                'S' => {
+                    let (rest, data) = Self::parse_tag_bytes::<3>(remaining, "S")?;
+                    tag_data.unknown_s = Some(data);
+                    remaining = rest;
+                }
+                'U' => {
+                    let (rest, data) = Self::parse_tag_u16(remaining, "U")?;
+                    tag_data.superfx_clock = Some(data);
+                    remaining = rest;
+                }
+                'a' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "a")?;
+                    tag_data.armet_version = Some(data);
+                    remaining = rest;
+                }
+                'c' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "c")?;
+                    tag_data.snes_header_location = Some(data);
+                    remaining = rest;
+                }
+                'd' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "d")?;
+                    tag_data.unknown_d = Some(data);
+                    remaining = rest;
+                }
+                'e' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "e")?;
+                    tag_data.enhancement_chip = Some(data);
+                    remaining = rest;
+                }
+                'h' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "h")?;
+                    tag_data.resolution_ratio = Some(data);
+                    remaining = rest;
+                }
+                'j' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "j")?;
+                    tag_data.unknown_j = Some(data);
+                    remaining = rest;
+                }
+                'm' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "m")?;
+                    tag_data.mouse_flag = Some(data);
+                    remaining = rest;
+                }
+                'p' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "p")?;
+                    tag_data.max_players = Some(data);
+                    remaining = rest;
+                }
+                'r' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "r")?;
+                    tag_data.visible_height = Some(data);
+                    remaining = rest;
+                }
+                't' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "t")?;
+                    tag_data.unknown_t = Some(data);
+                    remaining = rest;
+                }
+                'v' => {
+                    let (rest, data) = Self::parse_tag_u8(remaining, "v")?;
+                    tag_data.volume = Some(data);
+                    remaining = rest;
                 }
-                // Add more tag matches...
                 _ => break,
             }
         }
 
         Ok((remaining, tag_data))
     }
+
+    /// Number of bytes this tag block serializes to.
+    fn encoded_len(&self) -> usize {
+        let mut len = 0;
+        if self.armet_threshold.is_some() {
+            len += 1 + 3;
+        }
+        if let Some(data) = &self.sdd1_data {
+            len += 1 + 3 + data.len();
+        }
+        if self.preset_id.is_some() {
+            len += 1 + 3 + 2;
+        }
+        if self.flags.is_some() {
+            len += 1 + 7;
+        }
+        if self.unknown_s.is_some() {
+            len += 1 + 3;
+        }
+        if self.superfx_clock.is_some() {
+            len += 1 + 2;
+        }
+        // The remaining tags are all a single value byte.
+        for present in [
+            self.armet_version.is_some(),
+            self.snes_header_location.is_some(),
+            self.unknown_d.is_some(),
+            self.enhancement_chip.is_some(),
+            self.resolution_ratio.is_some(),
+            self.unknown_j.is_some(),
+            self.mouse_flag.is_some(),
+            self.max_players.is_some(),
+            self.visible_height.is_some(),
+            self.unknown_t.is_some(),
+            self.volume.is_some(),
+        ] {
+            if present {
+                len += 1 + 1;
+            }
+        }
+        len
+    }
+
+    /// Serialize every present tag, in the struct's field order.
+    fn write_game_tags<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if let Some(threshold) = &self.armet_threshold {
+            writer.write_all(b"A")?;
+            writer.write_all(threshold)?;
+        }
+        if let Some(data) = &self.sdd1_data {
+            writer.write_all(b"D")?;
+            writer.write_all(&(data.len() as u32).to_le_bytes()[0..3])?;
+            writer.write_all(data)?;
+        }
+        if let Some(preset_id) = self.preset_id {
+            writer.write_all(b"G")?;
+            writer.write_all(&[0u8; 3])?; // reserved
+            writer.write_all(&preset_id.to_le_bytes())?;
+        }
+        if let Some(flags) = &self.flags {
+            writer.write_all(b"P")?;
+            writer.write_all(flags)?;
+        }
+        if let Some(data) = &self.unknown_s {
+            writer.write_all(b"S")?;
+            writer.write_all(data)?;
+        }
+        if let Some(clock) = self.superfx_clock {
+            writer.write_all(b"U")?;
+            writer.write_all(&clock.to_le_bytes())?;
+        }
+        for (letter, value) in [
+            (b'a', self.armet_version),
+            (b'c', self.snes_header_location),
+            (b'd', self.unknown_d),
+            (b'e', self.enhancement_chip),
+            (b'h', self.resolution_ratio),
+            (b'j', self.unknown_j),
+            (b'm', self.mouse_flag),
+            (b'p', self.max_players),
+            (b'r', self.visible_height),
+            (b't', self.unknown_t),
+            (b'v', self.volume),
+        ] {
+            if let Some(value) = value {
+                writer.write_all(&[letter, value])?;
+            }
+        }
+        Ok(())
+    }
 }
 
 // Helper function for 24-bit little endian integers
@@ -274,14 +655,17 @@ impl Sfrom {
 
         // Extract ROM data from the specified offset to PCM samples location
         // (or footer location if no PCM data)
+        // Header offsets are absolute file positions, while `input` now starts
+        // just past the header, so rebase them onto `input`.
+        let body = |offset: U32| (offset.get() as usize).saturating_sub(size_of::<SfromHeader>());
         let rom_end = if header.pcm_samples_location != header.footer_location {
-            header.pcm_samples_location as usize
+            body(header.pcm_samples_location)
         } else {
-            header.footer_location as usize
+            body(header.footer_location)
         };
 
         // let rom_size = rom_end - header.rom_location as usize;
-        let rom_start = header.rom_location as usize;
+        let rom_start = body(header.rom_location);
 
         // Ensure we have enough data
         if input.len() < rom_end {
@@ -296,12 +680,12 @@ impl Sfrom {
 
         // Extract PCM data if present
         let (pcm_data, pcm_footer) = if header.pcm_samples_location != header.footer_location {
-            let pcm_start = header.pcm_samples_location as usize;
-            let pcm_end = header.pcm_footer_location as usize;
+            let pcm_start = body(header.pcm_samples_location);
+            let pcm_end = body(header.pcm_footer_location);
             let pcm_data = input[pcm_start..pcm_end].to_vec();
 
-            let pcm_footer_start = header.pcm_footer_location as usize;
-            let pcm_footer_end = header.footer_location as usize;
+            let pcm_footer_start = body(header.pcm_footer_location);
+            let pcm_footer_end = body(header.footer_location);
             let pcm_footer = input[pcm_footer_start..pcm_footer_end].to_vec();
 
             (Some(pcm_data), Some(pcm_footer))
@@ -310,7 +694,7 @@ impl Sfrom {
         };
 
         // Move to footer position
-        let footer_pos = header.footer_location as usize;
+        let footer_pos = body(header.footer_location);
         let footer_input = &input[footer_pos..];
 
         // Parse footer and game tags
@@ -332,7 +716,7 @@ impl Sfrom {
 
     pub fn write<W: Write + Seek>(&self, writer: &mut W) -> io::Result<()> {
         // Calculate all the necessary offsets and sizes first
-        let header_size = 0x30; // Standard header size
+        let header_size = size_of::<SfromHeader>(); // Standard header size
         let rom_start = header_size;
         let rom_end = rom_start + self.rom_data.len();
 
@@ -348,30 +732,27 @@ impl Sfrom {
             };
 
         // Calculate game tags size
-        let mut game_tags_size = 0;
-        if self.game_tags.armet_threshold.is_some() {
-            game_tags_size += 4;
-        }
-        if let Some(data) = &self.game_tags.sdd1_data {
-            game_tags_size += 4 + data.len();
-        }
-        // ... calculate sizes for other tags ...
+        let game_tags_size = self.game_tags.encoded_len();
+
+        let total_size = footer_start + size_of::<SfromFooter>() + game_tags_size;
 
-        let total_size = footer_start + 0x23 + game_tags_size;
+        // Build the on-disk header with freshly computed offsets and blit it.
+        let header = SfromHeader {
+            magic: U32::new(0x100),
+            file_size: U32::new(total_size as u32),
+            rom_location: U32::new(rom_start as u32),
+            pcm_samples_location: U32::new(pcm_start as u32),
+            pcm_footer_location: U32::new(pcm_footer_start as u32),
+            footer_location: U32::new(footer_start as u32),
+            sdd1_data_offset: self.header.sdd1_data_offset,
+            reserved1: U32::new(0),
+            unknown1: self.header.unknown1,
+            wiiu_game_id: self.header.wiiu_game_id,
+            reserved2: U32::new(0),
+        };
 
-        // Seek to start and write header
         writer.seek(SeekFrom::Start(0))?;
-        writer.write_all(&0x100u32.to_le_bytes())?; // magic
-        writer.write_all(&(total_size as u32).to_le_bytes())?;
-        writer.write_all(&(rom_start as u32).to_le_bytes())?;
-        writer.write_all(&(pcm_start as u32).to_le_bytes())?;
-        writer.write_all(&(pcm_footer_start as u32).to_le_bytes())?;
-        writer.write_all(&(footer_start as u32).to_le_bytes())?;
-        writer.write_all(&self.header.sdd1_data_offset.to_le_bytes())?;
-        writer.write_all(&0u32.to_le_bytes())?; // reserved1
-        writer.write_all(&self.header.unknown1.to_le_bytes())?;
-        writer.write_all(&self.header.wiiu_game_id)?;
-        writer.write_all(&0u32.to_le_bytes())?; // reserved2
+        writer.write_all(header.as_bytes())?;
 
         // Seek to ROM start and write ROM data
         writer.seek(SeekFrom::Start(rom_start as u64))?;
@@ -385,40 +766,160 @@ impl Sfrom {
             writer.write_all(pcm_footer)?;
         }
 
-        // Seek to footer position and write footer
+        // Seek to footer position and blit the footer in one shot.
         writer.seek(SeekFrom::Start(footer_start as u64))?;
-        writer.write_all(&[self.footer.fps])?;
-        writer.write_all(&self.footer.rom_size.to_le_bytes())?;
-        writer.write_all(&self.footer.pcm_samples_size.to_le_bytes())?;
-        writer.write_all(&self.footer.pcm_footer_size.to_le_bytes())?;
-        writer.write_all(&self.footer.preset_id.to_le_bytes())?;
-        writer.write_all(&[
-            self.footer.player_count,
-            self.footer.sound_volume,
-            self.footer.rom_type,
-            self.footer.enhancement_chip,
-        ])?;
-        writer.write_all(&self.footer.unknown1.to_le_bytes())?;
-        writer.write_all(&self.footer.unknown2.to_le_bytes())?;
+        writer.write_all(self.footer.as_bytes())?;
 
         // Write game tags immediately after footer
-        self.write_game_tags(writer)?;
+        self.game_tags.write_game_tags(writer)?;
 
         Ok(())
     }
 
-    fn write_game_tags<W: Write>(&self, writer: &mut W) -> io::Result<()> {
-        // Write tags in order
-        if let Some(threshold) = &self.game_tags.armet_threshold {
-            writer.write_all(b"A")?;
-            writer.write_all(threshold)?;
+    /// Build a fully-populated, writable [`Sfrom`] from raw SNES ROM bytes.
+    ///
+    /// The SNES internal header is decoded to fill in the footer's
+    /// `rom_type`/`enhancement_chip`/`rom_size`, and the header offsets are
+    /// laid out with the ROM at 0x30 and no PCM block (so the PCM locations
+    /// collapse onto the footer). `GameTagData` is left empty apart from the
+    /// `'e'` enhancement-chip and `'p'` max-players tags implied by the
+    /// header. Returns `None` when the ROM has no recognisable header.
+    pub fn from_rom(rom: &[u8]) -> Option<Self> {
+        let snes = SnesRomHeader::parse(rom)?;
+
+        // Store the ROM without any SMC copier header.
+        let rom_data = if rom.len() % 0x8000 == 0x200 {
+            rom[0x200..].to_vec()
+        } else {
+            rom.to_vec()
+        };
+
+        let enhancement_chip = snes.enhancement_chip();
+        let rom_size = rom_data.len() as u32;
+        // Placeholder to match `GameTitle::from_rom`: the SNES header carries
+        // no player count, so both builders default to solo.
+        let player_count = 1;
+
+        let game_tags = GameTagData {
+            enhancement_chip: Some(enhancement_chip as u8), // Tag 'e'
+            max_players: Some(player_count),                // Tag 'p'
+            ..GameTagData::default()
+        };
+
+        let header_size = size_of::<SfromHeader>() as u32;
+        // No PCM block: the PCM locations collapse onto the footer location.
+        let footer_location = header_size + rom_size;
+        // Mirror what `write` will lay down so the header is self-consistent.
+        let file_size = footer_location + size_of::<SfromFooter>() as u32 + game_tags.encoded_len() as u32;
+
+        let header = SfromHeader {
+            magic: U32::new(0x100),
+            file_size: U32::new(file_size),
+            rom_location: U32::new(header_size),
+            pcm_samples_location: U32::new(footer_location),
+            pcm_footer_location: U32::new(footer_location),
+            footer_location: U32::new(footer_location),
+            sdd1_data_offset: U32::new(0),
+            reserved1: U32::new(0),
+            unknown1: U32::new(1),
+            wiiu_game_id: [0; 8],
+            reserved2: U32::new(0),
+        };
+
+        let footer = SfromFooter {
+            fps: 0x3C,
+            rom_size: U32::new(rom_size),
+            pcm_samples_size: U32::new(0),
+            pcm_footer_size: U32::new(0),
+            preset_id: U16::new(0),
+            player_count,
+            sound_volume: 0x7F,
+            rom_type: snes.rom_type(),
+            enhancement_chip: enhancement_chip as u8,
+            unknown1: U32::new(1),
+            unknown2: U32::new(1),
+        };
+
+        Some(Sfrom {
+            header,
+            rom_data,
+            pcm_data: None,
+            pcm_footer: None,
+            footer,
+            game_tags,
+        })
+    }
+
+    /// Validate a parsed SFROM and report the first structural or checksum
+    /// problem found, leaving the caller to surface it however it likes.
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        let magic = self.header.magic.get();
+        if magic != 0x100 {
+            return Err(VerifyError::BadMagic(magic));
         }
-        if let Some(data) = &self.game_tags.sdd1_data {
-            writer.write_all(b"D")?;
-            writer.write_all(&(data.len() as u32).to_le_bytes()[0..3])?;
-            writer.write_all(data)?;
+
+        // Offsets must grow monotonically and stay within the file.
+        let rom = self.header.rom_location.get();
+        let pcm = self.header.pcm_samples_location.get();
+        let pcm_footer = self.header.pcm_footer_location.get();
+        let footer = self.header.footer_location.get();
+        let file_size = self.header.file_size.get();
+        if !(rom <= pcm && pcm <= pcm_footer && pcm_footer <= footer && footer <= file_size) {
+            return Err(VerifyError::NonMonotonicOffsets(format!(
+                "rom={rom:#x} pcm={pcm:#x} pcm_footer={pcm_footer:#x} \
+                 footer={footer:#x} file_size={file_size:#x}"
+            )));
+        }
+
+        // Declared sizes must match the actual slices.
+        if self.footer.rom_size.get() as usize != self.rom_data.len() {
+            return Err(VerifyError::RomSizeMismatch {
+                declared: self.footer.rom_size.get(),
+                actual: self.rom_data.len(),
+            });
+        }
+        let pcm_len = self.pcm_data.as_ref().map_or(0, Vec::len);
+        if self.footer.pcm_samples_size.get() as usize != pcm_len {
+            return Err(VerifyError::PcmSizeMismatch {
+                kind: "samples",
+                declared: self.footer.pcm_samples_size.get(),
+                actual: pcm_len,
+            });
+        }
+        let pcm_footer_len = self.pcm_footer.as_ref().map_or(0, Vec::len);
+        if self.footer.pcm_footer_size.get() as usize != pcm_footer_len {
+            return Err(VerifyError::PcmSizeMismatch {
+                kind: "footer",
+                declared: self.footer.pcm_footer_size.get(),
+                actual: pcm_footer_len,
+            });
+        }
+
+        // Recompute the SNES checksum and compare against the internal header.
+        // Strip any copier header first so the offset and the byte sum both
+        // operate on the same slice `SnesRomHeader::parse` used.
+        let rom = if self.rom_data.len() % 0x8000 == 0x200 {
+            &self.rom_data[0x200..]
+        } else {
+            &self.rom_data[..]
+        };
+        let snes = SnesRomHeader::parse(rom).ok_or(VerifyError::MissingSnesHeader)?;
+        let offset = snes.header_offset();
+        let stored_complement = u16::from_le_bytes([rom[offset + 0x1C], rom[offset + 0x1D]]);
+        let stored_checksum = u16::from_le_bytes([rom[offset + 0x1E], rom[offset + 0x1F]]);
+        let computed = SnesRomHeader::checksum(rom);
+        if computed != stored_checksum {
+            return Err(VerifyError::ChecksumMismatch {
+                computed,
+                stored: stored_checksum,
+            });
+        }
+        if stored_complement ^ stored_checksum != 0xFFFF {
+            return Err(VerifyError::ComplementMismatch {
+                complement: stored_complement,
+                checksum: stored_checksum,
+            });
         }
-        // ... write other tags ...
 
         Ok(())
     }
@@ -427,8 +928,177 @@ impl Sfrom {
         let mut file = std::fs::File::create(path)?;
 
         // Pre-allocate the file size
-        file.set_len(self.header.file_size as u64)?;
+        file.set_len(self.header.file_size.get() as u64)?;
 
         self.write(&mut file)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Build a minimal 32KiB LoROM image with a valid internal header.
+    fn lorom_image() -> Vec<u8> {
+        let mut rom = vec![0u8; 0x8000];
+        let base = 0x7FC0;
+        // Printable 21-byte title.
+        rom[base..base + 0x15].copy_from_slice(b"TEST ROM             ");
+        rom[base + 0x15] = 0x20; // map mode: LoROM (low nibble 0)
+        rom[base + 0x16] = 0x00; // chipset: ROM only
+        rom[base + 0x17] = 0x07; // rom size byte
+        rom[base + 0x18] = 0x00; // no SRAM
+        rom[base + 0x1A] = b'0';
+        rom[base + 0x1B] = b'1';
+        // Complement and checksum must XOR to 0xFFFF.
+        rom[base + 0x1C..base + 0x1E].copy_from_slice(&0x1234u16.to_le_bytes());
+        rom[base + 0x1E..base + 0x20].copy_from_slice(&0xEDCBu16.to_le_bytes());
+        // Reset vector pointing into ROM space.
+        rom[base + 0x3C..base + 0x3E].copy_from_slice(&0x8000u16.to_le_bytes());
+        rom
+    }
+
+    #[test]
+    fn from_rom_detects_lorom() {
+        let rom = lorom_image();
+        let sfrom = Sfrom::from_rom(&rom).expect("header should parse");
+        assert_eq!(sfrom.footer.rom_type, 0x14);
+        assert_eq!(sfrom.footer.enhancement_chip, EnhancementChip::Normal as u8);
+        assert_eq!(sfrom.rom_data.len(), rom.len());
+    }
+
+    #[test]
+    fn from_rom_write_parse_round_trip() {
+        let rom = lorom_image();
+        let sfrom = Sfrom::from_rom(&rom).expect("header should parse");
+
+        let mut buf = Cursor::new(Vec::new());
+        sfrom.write(&mut buf).expect("write should succeed");
+
+        let bytes = buf.into_inner();
+        let (_, parsed) = Sfrom::parse(&bytes).expect("parse should succeed");
+
+        assert_eq!(parsed.rom_data, sfrom.rom_data);
+        assert_eq!(parsed.footer.rom_size.get(), sfrom.footer.rom_size.get());
+        assert_eq!(parsed.footer.rom_type, sfrom.footer.rom_type);
+        assert_eq!(parsed.footer.enhancement_chip, sfrom.footer.enhancement_chip);
+    }
+
+    /// Round-trip every tag field through the writer and parser. The tag
+    /// block is self-delimiting, so `parse` must recover the exact same
+    /// `GameTagData` it was handed, for any combination of present tags.
+    fn assert_tag_round_trip(tags: &GameTagData) {
+        let mut buf = Vec::new();
+        tags.write_game_tags(&mut buf).expect("write should succeed");
+        assert_eq!(buf.len(), tags.encoded_len());
+
+        let (rest, parsed) = GameTagData::parse(&buf).expect("parse should succeed");
+        assert!(rest.is_empty());
+        assert_eq!(&parsed, tags);
+    }
+
+    /// A LoROM image with a self-consistent checksum written into its header.
+    fn valid_lorom_image() -> Vec<u8> {
+        let mut rom = lorom_image();
+        let base = 0x7FC0;
+        rom[base + 0x1C..base + 0x20].fill(0);
+        // The checksum field always contributes 0x1FE to the total (each of
+        // its two byte-pairs sums to 0xFF), so this is a fixpoint.
+        let checksum = SnesRomHeader::checksum(&rom).wrapping_add(0x01FE);
+        rom[base + 0x1E..base + 0x20].copy_from_slice(&checksum.to_le_bytes());
+        rom[base + 0x1C..base + 0x1E].copy_from_slice(&(!checksum).to_le_bytes());
+        rom
+    }
+
+    #[test]
+    fn verify_accepts_consistent_rom() {
+        let sfrom = Sfrom::from_rom(&valid_lorom_image()).expect("header should parse");
+        sfrom.verify().expect("a consistent SFROM should verify");
+    }
+
+    #[test]
+    fn verify_rejects_rom_size_mismatch() {
+        let mut sfrom = Sfrom::from_rom(&valid_lorom_image()).expect("header should parse");
+        sfrom.footer.rom_size = U32::new(sfrom.footer.rom_size.get() + 1);
+        assert!(matches!(
+            sfrom.verify(),
+            Err(VerifyError::RomSizeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn verify_rejects_bad_magic() {
+        let mut sfrom = Sfrom::from_rom(&valid_lorom_image()).expect("header should parse");
+        sfrom.header.magic = U32::new(0xDEAD);
+        assert!(matches!(sfrom.verify(), Err(VerifyError::BadMagic(0xDEAD))));
+    }
+
+    /// Tiny deterministic xorshift PRNG so the property test stays reproducible
+    /// without pulling in a generator crate.
+    fn next_rand(state: &mut u64) -> u64 {
+        let mut x = *state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        *state = x;
+        x
+    }
+
+    /// Build an arbitrary `GameTagData`, independently deciding each field's
+    /// presence and value from the PRNG.
+    fn arbitrary_tags(state: &mut u64) -> GameTagData {
+        // Each field is present with ~50% probability.
+        fn present(state: &mut u64) -> bool {
+            next_rand(state) & 1 == 1
+        }
+        fn opt_u8(state: &mut u64) -> Option<u8> {
+            present(state).then(|| next_rand(state) as u8)
+        }
+
+        GameTagData {
+            armet_threshold: present(state)
+                .then(|| [next_rand(state) as u8, next_rand(state) as u8, next_rand(state) as u8]),
+            sdd1_data: present(state).then(|| {
+                let len = (next_rand(state) % 8) as usize;
+                (0..len).map(|_| next_rand(state) as u8).collect()
+            }),
+            preset_id: present(state).then(|| next_rand(state) as u16),
+            flags: present(state).then(|| {
+                let mut f = [0u8; 7];
+                f.iter_mut().for_each(|b| *b = next_rand(state) as u8);
+                f
+            }),
+            unknown_s: present(state)
+                .then(|| [next_rand(state) as u8, next_rand(state) as u8, next_rand(state) as u8]),
+            superfx_clock: present(state).then(|| next_rand(state) as u16),
+            armet_version: opt_u8(state),
+            snes_header_location: opt_u8(state),
+            unknown_d: opt_u8(state),
+            enhancement_chip: opt_u8(state),
+            resolution_ratio: opt_u8(state),
+            unknown_j: opt_u8(state),
+            mouse_flag: opt_u8(state),
+            max_players: opt_u8(state),
+            visible_height: opt_u8(state),
+            unknown_t: opt_u8(state),
+            volume: opt_u8(state),
+        }
+    }
+
+    #[test]
+    fn game_tags_round_trip() {
+        // Edge cases that a random run might miss.
+        assert_tag_round_trip(&GameTagData::default());
+        assert_tag_round_trip(&GameTagData {
+            sdd1_data: Some(Vec::new()),
+            ..GameTagData::default()
+        });
+
+        // Property: any arbitrary tag set survives a write/parse round-trip.
+        let mut state = 0x9E3779B97F4A7C15;
+        for _ in 0..2000 {
+            assert_tag_round_trip(&arbitrary_tags(&mut state));
+        }
+    }
+}
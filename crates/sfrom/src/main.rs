@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use sfrom::{GameTagData, SnesRomHeader, Sfrom};
+
+/// Inspect, extract, build and verify SNES Online (SFROM) files.
+#[derive(Parser)]
+#[command(name = "sfrom", about, version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the decoded header, footer and game tags of an SFROM file.
+    Info { file: PathBuf },
+    /// Extract the raw ROM (and PCM blocks, if present) into a directory.
+    Extract { file: PathBuf, outdir: PathBuf },
+    /// Build an SFROM file from a raw SNES ROM.
+    Convert { rom: PathBuf, out: PathBuf },
+    /// Check an SFROM file for structural and checksum errors.
+    Verify { file: PathBuf },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Info { file } => info(&file),
+        Command::Extract { file, outdir } => extract(&file, &outdir),
+        Command::Convert { rom, out } => convert(&rom, &out),
+        Command::Verify { file } => return verify(&file),
+    };
+
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Parse an SFROM file off disk, discarding the trailing slice.
+fn load(file: &Path) -> Result<Sfrom, String> {
+    let data = std::fs::read(file).map_err(|e| format!("{}: {e}", file.display()))?;
+    let (_, sfrom) = Sfrom::parse(&data).map_err(|e| format!("{}: {e}", file.display()))?;
+    Ok(sfrom)
+}
+
+fn info(file: &Path) -> Result<(), String> {
+    let sfrom = load(file)?;
+
+    println!("Header: {:#?}", sfrom.header);
+    println!("Footer: {:#?}", sfrom.footer);
+
+    if let Some(snes) = SnesRomHeader::parse(&sfrom.rom_data) {
+        println!("Detected map mode: {:?}", snes.map_mode);
+        println!("Detected rom_type: {:#04x}", snes.rom_type());
+        println!("Enhancement chip:  {:?}", snes.enhancement_chip());
+    } else {
+        println!("Detected map mode: <no SNES header found>");
+    }
+
+    println!("Game tags: {}", present_tags(&sfrom.game_tags).join(", "));
+    Ok(())
+}
+
+fn extract(file: &Path, outdir: &Path) -> Result<(), String> {
+    let sfrom = load(file)?;
+    std::fs::create_dir_all(outdir).map_err(|e| format!("{}: {e}", outdir.display()))?;
+
+    let write = |name: &str, data: &[u8]| -> Result<(), String> {
+        let path = outdir.join(name);
+        std::fs::write(&path, data).map_err(|e| format!("{}: {e}", path.display()))?;
+        println!("wrote {} ({} bytes)", path.display(), data.len());
+        Ok(())
+    };
+
+    write("rom.sfc", &sfrom.rom_data)?;
+    if let Some(pcm) = &sfrom.pcm_data {
+        write("pcm.bin", pcm)?;
+    }
+    if let Some(pcm_footer) = &sfrom.pcm_footer {
+        write("pcm_footer.bin", pcm_footer)?;
+    }
+    Ok(())
+}
+
+fn convert(rom: &Path, out: &Path) -> Result<(), String> {
+    let data = std::fs::read(rom).map_err(|e| format!("{}: {e}", rom.display()))?;
+    let sfrom = Sfrom::from_rom(&data)
+        .ok_or_else(|| format!("{}: no recognisable SNES header", rom.display()))?;
+    let out = out.to_str().ok_or("output path is not valid UTF-8")?;
+    sfrom.save_to_file(out).map_err(|e| format!("{out}: {e}"))?;
+    println!("wrote {out}");
+    Ok(())
+}
+
+fn verify(file: &Path) -> ExitCode {
+    let sfrom = match load(file) {
+        Ok(sfrom) => sfrom,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match sfrom.verify() {
+        Ok(()) => {
+            println!("{}: ok", file.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}: {err}", file.display());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// List the letters of the tags that are present in `tags`.
+fn present_tags(tags: &GameTagData) -> Vec<&'static str> {
+    let mut present = Vec::new();
+    let mut push = |set: bool, letter: &'static str| {
+        if set {
+            present.push(letter);
+        }
+    };
+    push(tags.armet_threshold.is_some(), "A");
+    push(tags.sdd1_data.is_some(), "D");
+    push(tags.preset_id.is_some(), "G");
+    push(tags.flags.is_some(), "P");
+    push(tags.unknown_s.is_some(), "S");
+    push(tags.superfx_clock.is_some(), "U");
+    push(tags.armet_version.is_some(), "a");
+    push(tags.snes_header_location.is_some(), "c");
+    push(tags.unknown_d.is_some(), "d");
+    push(tags.enhancement_chip.is_some(), "e");
+    push(tags.resolution_ratio.is_some(), "h");
+    push(tags.unknown_j.is_some(), "j");
+    push(tags.mouse_flag.is_some(), "m");
+    push(tags.max_players.is_some(), "p");
+    push(tags.visible_height.is_some(), "r");
+    push(tags.unknown_t.is_some(), "t");
+    push(tags.volume.is_some(), "v");
+    present
+}
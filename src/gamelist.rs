@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 use serde::{Serialize, Deserialize};
 use chrono::NaiveDate;
+use sfrom::SnesRomHeader;
 
 const BLANK_CHAR: char = 'ー';
 
@@ -140,6 +141,111 @@ pub fn sanitize_sort_title(title: &str) -> String {
     title.to_lowercase().replace(' ', "_")
 }
 
+/// Resolve the two-character SNES maker code (header offset 0x1A, base-36
+/// ASCII) to a human-readable publisher.
+///
+/// Unknown codes fall back to `"Unknown"` rather than failing.
+pub fn publisher_from_maker_code(code: [u8; 2]) -> &'static str {
+    match &code {
+        b"01" | b"31" => "Nintendo",
+        b"08" => "Capcom",
+        b"13" | b"69" => "Electronic Arts",
+        b"18" | b"38" => "Hudson Soft",
+        b"1P" => "Creatures",
+        b"20" => "KSS",
+        b"22" => "POW",
+        b"28" => "Kemco",
+        b"29" => "Seta",
+        b"30" => "Viacom",
+        b"32" => "Bandai",
+        b"33" | b"93" => "Ocean/Acclaim",
+        b"34" | b"54" | b"A4" => "Konami",
+        b"37" => "Taito",
+        b"39" => "Banpresto",
+        b"41" => "Ubi Soft",
+        b"42" => "Atlus",
+        b"46" => "Angel",
+        b"49" => "Irem",
+        b"4Y" => "Rare",
+        b"50" => "Absolute",
+        b"51" => "Acclaim",
+        b"52" => "Activision",
+        b"53" => "American Sammy",
+        b"56" => "LJN",
+        b"60" => "Titus",
+        b"61" => "Virgin",
+        b"64" => "LucasArts",
+        b"67" => "Ocean",
+        b"70" => "Infogrames",
+        b"71" => "Interplay",
+        b"72" => "Broderbund",
+        b"78" => "THQ",
+        b"79" => "Accolade",
+        b"83" => "LOZC",
+        b"8B" => "Bullet-Proof Software",
+        b"91" => "Chunsoft",
+        b"92" => "Video System",
+        b"95" => "Varie",
+        b"97" => "Kaneko",
+        b"99" => "Pack-In-Video",
+        _ => "Unknown",
+    }
+}
+
+impl GameTitle {
+    /// Build a partial [`GameTitle`] from raw SNES ROM bytes, filling in the
+    /// fields the cartridge header can actually supply (title and publisher
+    /// from the internal header, SRAM size) and leaving the rest at placeholder
+    /// defaults for the operator to complete.
+    ///
+    /// Note that the SNES internal header carries no player count, so
+    /// `players_count` is left at the placeholder `1` rather than derived.
+    ///
+    /// Returns `None` when the ROM has no recognisable internal header.
+    pub fn from_rom(rom: &[u8]) -> Option<Self> {
+        let header = SnesRomHeader::parse(rom)?;
+
+        let title = String::from_utf8_lossy(&header.title)
+            .trim_end_matches(['\0', ' '])
+            .to_string();
+        let publisher = publisher_from_maker_code(header.maker_code).to_string();
+
+        Some(GameTitle {
+            code: String::new(),
+            compatible_titles: None,
+            connect_guides: None,
+            copyright: String::new(),
+            cover: String::new(),
+            details_screen: String::new(),
+            display_version: None,
+            fadein: None,
+            hidden_countries: None,
+            lcla6_release_date: NaiveDate::from_ymd_opt(1970, 1, 1).unwrap(),
+            onecartridge_guides: None,
+            // Placeholder: the internal header carries no player count.
+            players_count: 1,
+            sort_publisher: sanitize_sort_title(&publisher),
+            publisher,
+            release_date: String::new(),
+            rewind_interval: 0.0,
+            rom: String::new(),
+            save_count: 0,
+            simultaneous: false,
+            sort_title: sanitize_sort_title(&title),
+            sram_file_size: (header.sram_size > 0).then_some(header.sram_size as i32),
+            startup_state: None,
+            title,
+            title_ko: None,
+            title_zh_hans: None,
+            title_zh_hant: None,
+            volume: 0,
+            adjust_colors: None,
+            anothertitle_guides: None,
+            transfer_title: None,
+        })
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use super::*;